@@ -0,0 +1,257 @@
+use std::path::Path;
+
+use iced::highlighter;
+use iced::keyboard::key::Named;
+use iced::keyboard::{Key, Modifiers};
+use serde::Deserialize;
+
+use crate::Message;
+
+/// The name of the config file, looked up relative to the current working
+/// directory at startup.
+const CONFIG_PATH: &str = "config.toml";
+
+/// User-facing settings loaded from [`CONFIG_PATH`], with every field
+/// optional in the file and backed by [`Config::default`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct Config {
+    pub theme: String,
+    pub font: String,
+    pub auto_save: bool,
+    pub auto_pairs: bool,
+    pub keybindings: Vec<KeyBinding>,
+}
+
+impl Config {
+    /// Reads [`CONFIG_PATH`], falling back to [`Config::default`] when it's
+    /// missing or malformed. The second element is a human-readable parse
+    /// error, meant to be surfaced in the status bar rather than panicking.
+    pub fn load() -> (Self, Option<String>) {
+        let raw = match std::fs::read_to_string(Path::new(CONFIG_PATH)) {
+            Ok(raw) => raw,
+            Err(_) => return (Self::default(), None),
+        };
+
+        match toml::from_str(&raw) {
+            Ok(config) => (config, None),
+            Err(error) => (Self::default(), Some(error.to_string())),
+        }
+    }
+
+    /// The [`highlighter::Theme`] named by `self.theme`, falling back to the
+    /// default theme if the name isn't recognized.
+    pub fn theme(&self) -> highlighter::Theme {
+        highlighter::Theme::ALL
+            .iter()
+            .find(|theme| theme.to_string() == self.theme)
+            .copied()
+            .unwrap_or(highlighter::Theme::SolarizedDark)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theme: highlighter::Theme::SolarizedDark.to_string(),
+            font: String::from("JetBrains Mono"),
+            auto_save: true,
+            auto_pairs: true,
+            keybindings: KeyBinding::defaults(),
+        }
+    }
+}
+
+/// A single `(key, modifiers) -> action` mapping.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct KeyBinding {
+    pub key: String,
+    pub control: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub action: Action,
+}
+
+impl Default for KeyBinding {
+    fn default() -> Self {
+        Self {
+            key: String::new(),
+            control: false,
+            shift: false,
+            alt: false,
+            action: Action::Save,
+        }
+    }
+}
+
+impl KeyBinding {
+    /// The keybindings the editor ships with when `config.toml` doesn't
+    /// override them.
+    fn defaults() -> Vec<Self> {
+        vec![
+            Self {
+                key: String::from("s"),
+                control: true,
+                ..Self::default()
+            },
+            Self {
+                key: String::from("tab"),
+                control: true,
+                action: Action::NextTab,
+                ..Self::default()
+            },
+        ]
+    }
+
+    /// Whether a `keyboard::on_key_press` event matches this binding.
+    pub fn matches(&self, key: &Key, modifiers: Modifiers) -> bool {
+        if modifiers.control() != self.control
+            || modifiers.shift() != self.shift
+            || modifiers.alt() != self.alt
+        {
+            return false;
+        }
+
+        match key {
+            Key::Named(Named::Tab) => self.key.eq_ignore_ascii_case("tab"),
+            Key::Character(c) => c.as_str().eq_ignore_ascii_case(&self.key),
+            _ => false,
+        }
+    }
+}
+
+/// An action that a keybinding can trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Save,
+    Open,
+    New,
+    NewTab,
+    NextTab,
+    ExportHtml,
+}
+
+impl Action {
+    pub fn to_message(self) -> Message {
+        match self {
+            Action::Save => Message::Save,
+            Action::Open => Message::Open,
+            Action::New => Message::New,
+            Action::NewTab => Message::NewTab,
+            Action::NextTab => Message::NextTab,
+            Action::ExportHtml => Message::ExportHtml,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::keyboard::key::Named;
+    use iced::keyboard::{Key, Modifiers};
+
+    #[test]
+    fn valid_toml_overrides_the_defaults() {
+        let config: Config = toml::from_str(
+            r#"
+            theme = "Nord"
+            font = "Fira Code"
+            auto_save = false
+            auto_pairs = false
+
+            [[keybindings]]
+            key = "p"
+            control = true
+            shift = true
+            action = "export_html"
+            "#,
+        )
+        .expect("valid config should parse");
+
+        assert_eq!(config.theme, "Nord");
+        assert_eq!(config.font, "Fira Code");
+        assert!(!config.auto_save);
+        assert!(!config.auto_pairs);
+        assert_eq!(config.keybindings.len(), 1);
+        assert_eq!(config.keybindings[0].action, Action::ExportHtml);
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_their_defaults() {
+        let config: Config = toml::from_str("theme = \"Nord\"").expect("partial config should parse");
+
+        assert_eq!(config.theme, "Nord");
+        assert_eq!(config.font, Config::default().font);
+        assert_eq!(config.auto_save, Config::default().auto_save);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let result: Result<Config, _> = toml::from_str("theme = \"Nord\"\nnonsense = true");
+
+        assert!(result.is_err());
+    }
+
+    /// `Config::load` reads a fixed path relative to the process's current
+    /// directory, so the two tests that exercise it must not run
+    /// concurrently with each other (or they'd race on that global state).
+    static CWD_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    #[test]
+    fn load_falls_back_to_defaults_when_config_toml_is_missing() {
+        let _guard = CWD_LOCK.lock().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let original = std::env::current_dir().unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+
+        let (config, error) = Config::load();
+
+        std::env::set_current_dir(original).unwrap();
+
+        assert_eq!(config.theme, Config::default().theme);
+        assert!(error.is_none());
+    }
+
+    #[test]
+    fn load_surfaces_a_parse_error_and_falls_back_to_defaults_on_invalid_toml() {
+        let _guard = CWD_LOCK.lock().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(CONFIG_PATH), "theme = [not valid").unwrap();
+        let original = std::env::current_dir().unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+
+        let (config, error) = Config::load();
+
+        std::env::set_current_dir(original).unwrap();
+
+        assert_eq!(config.theme, Config::default().theme);
+        assert!(error.is_some());
+    }
+
+    #[test]
+    fn key_binding_matches_checks_key_and_modifiers() {
+        let binding = KeyBinding {
+            key: String::from("s"),
+            control: true,
+            ..KeyBinding::default()
+        };
+
+        assert!(binding.matches(&Key::Character("s".into()), Modifiers::CTRL));
+        assert!(!binding.matches(&Key::Character("s".into()), Modifiers::empty()));
+        assert!(!binding.matches(&Key::Character("d".into()), Modifiers::CTRL));
+    }
+
+    #[test]
+    fn key_binding_matches_the_named_tab_key() {
+        let binding = KeyBinding {
+            key: String::from("tab"),
+            control: true,
+            action: Action::NextTab,
+            ..KeyBinding::default()
+        };
+
+        assert!(binding.matches(&Key::Named(Named::Tab), Modifiers::CTRL));
+    }
+}
@@ -0,0 +1,243 @@
+use std::path::{Path, PathBuf};
+
+use iced::widget::text_editor;
+
+use crate::Error;
+
+/// Opening/closing delimiters that get auto-paired as you type.
+const DEFAULT_PAIRS: &[(char, char)] = &[
+    ('(', ')'),
+    ('{', '}'),
+    ('[', ']'),
+    ('"', '"'),
+    ('\'', '\''),
+    ('`', '`'),
+];
+
+fn is_quote(c: char) -> bool {
+    c == '"' || c == '\'' || c == '`'
+}
+
+/// A single open file buffer, shown as one tab in the editor.
+pub struct Document {
+    /// Stable identity for this tab, independent of its position in the tab
+    /// strip (which shifts whenever an earlier tab closes).
+    pub id: u64,
+    pub path: Option<PathBuf>,
+    pub content: text_editor::Content,
+    pub is_modified: bool,
+    pub error: Option<Error>,
+    /// Bumped on every edit, so a debounced auto-save can tell whether this
+    /// is still the edit it was scheduled for.
+    pub edit_version: u64,
+}
+
+impl Document {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id,
+            path: None,
+            content: text_editor::Content::new(),
+            is_modified: false,
+            error: None,
+            edit_version: 0,
+        }
+    }
+
+    pub fn opened(id: u64, path: PathBuf, content: &str) -> Self {
+        Self {
+            id,
+            content: text_editor::Content::with_text(content),
+            path: Some(path),
+            is_modified: false,
+            error: None,
+            edit_version: 0,
+        }
+    }
+
+    /// Resets this document back to an empty, unsaved buffer.
+    pub fn reset(&mut self) {
+        self.path = None;
+        self.content = text_editor::Content::new();
+        self.is_modified = true;
+    }
+
+    /// The label shown for this document in the tab strip and status bar.
+    pub fn title(&self) -> String {
+        self.path
+            .as_deref()
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| String::from("New File"))
+    }
+
+    /// The file extension used to pick a syntax highlighter, defaulting to
+    /// Rust for untitled buffers.
+    pub fn extension(&self) -> &str {
+        self.path
+            .as_ref()
+            .and_then(|path| path.extension()?.to_str())
+            .unwrap_or("rs")
+    }
+
+    /// Runs `action` against `self.content`, auto-closing bracket and quote
+    /// pairs along the way when `auto_pairs` is enabled.
+    pub fn perform_edit(&mut self, action: text_editor::Action, auto_pairs: bool) {
+        if action.is_edit() {
+            self.is_modified = true;
+            self.edit_version += 1;
+        }
+
+        if auto_pairs {
+            self.perform_with_auto_pairs(action);
+        } else {
+            self.content.perform(action);
+        }
+    }
+
+    fn perform_with_auto_pairs(&mut self, action: text_editor::Action) {
+        use text_editor::{Action, Edit, Motion};
+
+        match action {
+            Action::Edit(Edit::Insert(c)) => {
+                if let Some(&(_, closer)) = DEFAULT_PAIRS.iter().find(|(open, _)| *open == c) {
+                    if is_quote(c) && !self.quote_pair_allowed() {
+                        self.content.perform(Action::Edit(Edit::Insert(c)));
+                        return;
+                    }
+
+                    if c == closer && self.char_after_cursor() == Some(closer) {
+                        self.content.perform(Action::Move(Motion::Right));
+                        return;
+                    }
+
+                    self.content.perform(Action::Edit(Edit::Insert(c)));
+                    self.content.perform(Action::Edit(Edit::Insert(closer)));
+                    self.content.perform(Action::Move(Motion::Left));
+                    return;
+                }
+
+                if DEFAULT_PAIRS.iter().any(|(_, closer)| *closer == c)
+                    && self.char_after_cursor() == Some(c)
+                {
+                    self.content.perform(Action::Move(Motion::Right));
+                    return;
+                }
+
+                self.content.perform(Action::Edit(Edit::Insert(c)));
+            }
+            Action::Edit(Edit::Backspace) => {
+                let deletes_pair = match (self.char_before_cursor(), self.char_after_cursor()) {
+                    (Some(before), Some(after)) => DEFAULT_PAIRS
+                        .iter()
+                        .any(|&(open, closer)| open == before && closer == after),
+                    _ => false,
+                };
+
+                self.content.perform(action);
+
+                if deletes_pair {
+                    self.content.perform(Action::Edit(Edit::Delete));
+                }
+            }
+            _ => self.content.perform(action),
+        }
+    }
+
+    fn char_before_cursor(&self) -> Option<char> {
+        let (line, column) = self.content.cursor_position();
+        column.checked_sub(1).and_then(|index| {
+            self.content
+                .text()
+                .lines()
+                .nth(line)
+                .and_then(|line| line.chars().nth(index))
+        })
+    }
+
+    fn char_after_cursor(&self) -> Option<char> {
+        let (line, column) = self.content.cursor_position();
+        self.content
+            .text()
+            .lines()
+            .nth(line)
+            .and_then(|line| line.chars().nth(column))
+    }
+
+    /// Quotes only auto-pair at a word boundary, so they don't fight
+    /// apostrophes in the middle of prose.
+    fn quote_pair_allowed(&self) -> bool {
+        match self.char_before_cursor() {
+            None => true,
+            Some(c) => !c.is_alphanumeric() && c != '_',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use text_editor::{Action, Edit};
+
+    #[test]
+    fn typing_an_opener_auto_closes_and_lands_the_cursor_between() {
+        let mut document = Document::new(0);
+        document.perform_edit(Action::Edit(Edit::Insert('(')), true);
+
+        assert_eq!(document.content.text(), "()\n");
+        assert_eq!(document.content.cursor_position(), (0, 1));
+    }
+
+    #[test]
+    fn typing_a_closer_over_an_existing_one_swallows_instead_of_duplicating() {
+        let mut document = Document::new(0);
+        document.perform_edit(Action::Edit(Edit::Insert('(')), true);
+        document.perform_edit(Action::Edit(Edit::Insert(')')), true);
+
+        assert_eq!(document.content.text(), "()\n");
+        assert_eq!(document.content.cursor_position(), (0, 2));
+    }
+
+    #[test]
+    fn backspace_between_a_pair_deletes_both_delimiters() {
+        let mut document = Document::new(0);
+        document.perform_edit(Action::Edit(Edit::Insert('(')), true);
+        document.perform_edit(Action::Edit(Edit::Backspace), true);
+
+        assert_eq!(document.content.text(), "\n");
+        assert_eq!(document.content.cursor_position(), (0, 0));
+    }
+
+    #[test]
+    fn quote_mid_word_does_not_auto_pair() {
+        let mut document = Document::new(0);
+        document.perform_edit(Action::Edit(Edit::Insert('i')), true);
+        document.perform_edit(Action::Edit(Edit::Insert('t')), true);
+        document.perform_edit(Action::Edit(Edit::Insert('\'')), true);
+
+        assert_eq!(document.content.text(), "it'\n");
+        assert_eq!(document.content.cursor_position(), (0, 3));
+    }
+
+    #[test]
+    fn quote_after_closing_delimiter_auto_pairs() {
+        let mut document = Document::new(0);
+        document.perform_edit(Action::Edit(Edit::Insert('(')), true);
+        document.perform_edit(Action::Edit(Edit::Insert(')')), true);
+        document.perform_edit(Action::Edit(Edit::Insert('"')), true);
+
+        assert_eq!(document.content.text(), "()\"\"\n");
+        assert_eq!(document.content.cursor_position(), (0, 3));
+    }
+
+    #[test]
+    fn quote_after_punctuation_auto_pairs() {
+        let mut document = Document::new(0);
+        document.perform_edit(Action::Edit(Edit::Insert(',')), true);
+        document.perform_edit(Action::Edit(Edit::Insert('\'')), true);
+
+        assert_eq!(document.content.text(), ",''\n");
+        assert_eq!(document.content.cursor_position(), (0, 2));
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use iced::highlighter;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Color, Theme as SyntectTheme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+use crate::Error;
+
+/// Renders `text` as syntax-highlighted, self-contained HTML and writes it
+/// to `path`, prompting for a destination first if `path` is `None`.
+pub async fn export_html(
+    path: Option<PathBuf>,
+    text: String,
+    extension: String,
+    theme: highlighter::Theme,
+) -> Result<PathBuf, Error> {
+    let path = if let Some(path) = path {
+        path
+    } else {
+        rfd::AsyncFileDialog::new()
+            .set_title("Export as HTML...")
+            .add_filter("HTML", &["html"])
+            .set_file_name("export.html")
+            .save_file()
+            .await
+            .ok_or(Error::DialogClosed)
+            .map(|handle| handle.path().to_owned())?
+    };
+
+    let html = render_html(&text, &extension, theme);
+
+    tokio::fs::write(&path, html)
+        .await
+        .map_err(|error| Error::IOFailed(error.kind()))?;
+
+    Ok(path)
+}
+
+fn render_html(text: &str, extension: &str, theme: highlighter::Theme) -> String {
+    let syntax_set = SyntaxSet::load_defaults_newlines();
+    let theme_set = ThemeSet::load_defaults();
+    let syntect_theme = lookup_theme(&theme_set, theme);
+
+    let syntax = syntax_set
+        .find_syntax_by_extension(extension)
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+
+    let mut highlighter = HighlightLines::new(syntax, syntect_theme);
+    let background = color_to_css(syntect_theme.settings.background.unwrap_or(Color::WHITE));
+    let foreground = color_to_css(syntect_theme.settings.foreground.unwrap_or(Color::BLACK));
+
+    let mut body = String::new();
+    for line in LinesWithEndings::from(text) {
+        let Ok(ranges) = highlighter.highlight_line(line, &syntax_set) else {
+            continue;
+        };
+
+        for (style, span) in ranges {
+            let _ = write!(
+                body,
+                "<span style=\"color:{}\">{}</span>",
+                color_to_css(style.foreground),
+                escape_html(span)
+            );
+        }
+    }
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <style>\n\
+         body {{ background: {background}; color: {foreground}; }}\n\
+         pre {{ font-family: monospace; white-space: pre-wrap; }}\n\
+         </style>\n\
+         </head>\n\
+         <body>\n\
+         <pre>{body}</pre>\n\
+         </body>\n\
+         </html>\n"
+    )
+}
+
+/// Maps an iced `highlighter::Theme` to the syntect bundled theme it should
+/// render as. `highlighter::Theme`'s `Display` impl is formatted for the
+/// theme `pick_list` in the UI, not for syntect's `ThemeSet` keys, so the two
+/// names have to be matched up explicitly rather than by `to_string()`.
+fn syntect_theme_name(theme: highlighter::Theme) -> &'static str {
+    use highlighter::Theme;
+
+    match theme {
+        Theme::SolarizedDark => "Solarized (dark)",
+        Theme::Base16Mocha => "base16-mocha.dark",
+        Theme::Base16Ocean => "base16-ocean.dark",
+        Theme::Base16Eighties => "base16-eighties.dark",
+        Theme::Base16Light => "base16-ocean.light",
+        Theme::InspiredGithub => "InspiredGitHub",
+    }
+}
+
+/// Looks up the syntect theme matching iced's selected `theme`, falling back
+/// to whatever syntect bundles if the name isn't recognized.
+fn lookup_theme(theme_set: &ThemeSet, theme: highlighter::Theme) -> &SyntectTheme {
+    theme_set
+        .themes
+        .get(syntect_theme_name(theme))
+        .or_else(|| theme_set.themes.get("base16-ocean.dark"))
+        .or_else(|| theme_set.themes.values().next())
+        .expect("syntect bundles at least one default theme")
+}
+
+fn color_to_css(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn css_background(html: &str) -> &str {
+        html.split("background: ")
+            .nth(1)
+            .and_then(|rest| rest.split(';').next())
+            .expect("rendered HTML always sets a background color")
+    }
+
+    #[test]
+    fn render_html_background_follows_the_selected_theme() {
+        let dark = render_html("fn main() {}", "rs", highlighter::Theme::Base16Ocean);
+        let light = render_html("fn main() {}", "rs", highlighter::Theme::Base16Light);
+
+        assert_ne!(css_background(&dark), css_background(&light));
+    }
+}
@@ -0,0 +1,771 @@
+pub mod config;
+pub mod document;
+pub mod export;
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use iced::highlighter;
+use iced::keyboard;
+use iced::widget::{
+    button, column, container, horizontal_space, pick_list, row, text, text_editor, tooltip, Space,
+};
+use iced::{window, Element, Font, Length, Settings, Task, Theme};
+use iced_futures::{MaybeSend, Subscription};
+
+pub use config::Config;
+pub use document::Document;
+
+/// Runs the editor application. This is the only thing `main` calls.
+pub fn run() -> iced::Result {
+    init_tracing();
+
+    let (config, config_error) = Config::load();
+    let font_name: &'static str = Box::leak(config.font.clone().into_boxed_str());
+
+    iced::application(Editor::title, Editor::update, Editor::view)
+        .theme(Editor::theme)
+        .executor::<TokioExecutor>()
+        .settings(Settings {
+            default_font: Font::with_name(font_name),
+            fonts: vec![
+                include_bytes!("../fonts/editor-icon.ttf").as_slice().into(),
+                include_bytes!("../fonts/JetBrainsMono-Regular.ttf")
+                    .as_slice()
+                    .into(),
+            ],
+            ..Settings::default()
+        })
+        .subscription(Editor::subscription)
+        .run_with(move || Editor::initialize(config, config_error))
+}
+
+/// Sets up `tracing` so `RUST_LOG`-style filtering works, reading its level
+/// from `LOG_LEVEL` (defaulting to `warn`). Safe to call more than once, so
+/// tests can call it from every `#[test]`.
+pub fn init_tracing() {
+    let level = std::env::var("LOG_LEVEL").unwrap_or_else(|_| String::from("warn"));
+
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::new(level))
+        .with_test_writer()
+        .try_init();
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Edit(text_editor::Action),
+    Open,
+    New,
+    NewTab,
+    SelectTab(usize),
+    CloseTab(usize),
+    NextTab,
+    Save,
+    FileOpened(Result<(PathBuf, Arc<String>), Error>),
+    FileSaved {
+        id: u64,
+        result: Result<PathBuf, Error>,
+        pending: Option<PendingAction>,
+    },
+    ThemeSelected(highlighter::Theme),
+    CloseRequested(window::Id),
+    ConfirmDiscard {
+        pending: PendingAction,
+        choice: DiscardChoice,
+    },
+    AutoSaveTick {
+        id: u64,
+        version: u64,
+    },
+    ExportHtml,
+    HtmlExported(Result<PathBuf, Error>),
+    TabSaved {
+        id: u64,
+        result: Result<PathBuf, Error>,
+    },
+}
+
+/// How long to wait after the last edit before an auto-saved document is
+/// written to disk.
+const AUTO_SAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// A destructive action that was deferred behind an unsaved-changes prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingAction {
+    New,
+    CloseTab(usize),
+    Quit(window::Id),
+}
+
+/// The user's answer to the "save your changes?" dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscardChoice {
+    Save,
+    Discard,
+    Cancel,
+}
+
+pub struct Editor {
+    documents: Vec<Document>,
+    active: usize,
+    theme: highlighter::Theme,
+    auto_pairs: bool,
+    auto_save: bool,
+    next_document_id: u64,
+    keybindings: Vec<config::KeyBinding>,
+    pending_saves: usize,
+    pending_action: Option<PendingAction>,
+    config_error: Option<String>,
+    /// Set while the very first tab is still the blank placeholder
+    /// `Editor::new` seeds and a startup file load is in flight, so
+    /// `FileOpened` can tell "replace the placeholder" apart from a manual
+    /// `Open` (which should always add a new tab).
+    awaiting_startup_load: bool,
+}
+
+impl Editor {
+    pub fn new(config: Config) -> Self {
+        Self {
+            documents: vec![Document::new(0)],
+            active: 0,
+            theme: config.theme(),
+            auto_pairs: config.auto_pairs,
+            auto_save: config.auto_save,
+            next_document_id: 1,
+            keybindings: config.keybindings,
+            pending_saves: 0,
+            pending_action: None,
+            config_error: None,
+            awaiting_startup_load: false,
+        }
+    }
+
+    /// Hands out a fresh, stable id for a newly opened tab.
+    fn allocate_document_id(&mut self) -> u64 {
+        let id = self.next_document_id;
+        self.next_document_id += 1;
+        id
+    }
+
+    /// Builds the initial `Editor` and kicks off the default-file load.
+    /// Exposed (rather than private) so tests can drive the startup path
+    /// the same way `run()` does.
+    pub fn initialize(config: Config, config_error: Option<String>) -> (Self, Task<Message>) {
+        let mut editor = Self::new(config);
+        editor.config_error = config_error;
+        editor.awaiting_startup_load = true;
+
+        (
+            editor,
+            Task::perform(load_file(default_file()), Message::FileOpened),
+        )
+    }
+
+    /// All currently open documents, in tab order.
+    pub fn documents(&self) -> &[Document] {
+        &self.documents
+    }
+
+    pub fn active_document(&self) -> &Document {
+        &self.documents[self.active]
+    }
+
+    pub fn active_document_mut(&mut self) -> &mut Document {
+        &mut self.documents[self.active]
+    }
+
+    /// Looks up a document by its stable `id`, independent of its current
+    /// tab position. Used for results of async work (saves) that may
+    /// complete after the tab order or active tab has changed.
+    fn document_mut(&mut self, id: u64) -> Option<&mut Document> {
+        self.documents.iter_mut().find(|document| document.id == id)
+    }
+
+    /// Whether an auto-save tick scheduled for `id`/`version` still targets
+    /// the active document, i.e. it hasn't been superseded by a newer edit
+    /// or stopped being the active tab.
+    pub fn auto_save_tick_is_current(&self, id: u64, version: u64) -> bool {
+        let document = self.active_document();
+        document.id == id && document.edit_version == version
+    }
+
+    /// The status-bar error text: the config-load error (if any) and the
+    /// active document's error (if any), shown together so a lingering
+    /// config error can't permanently hide later document errors.
+    pub fn status_message(&self) -> String {
+        let config_error = self
+            .config_error
+            .as_ref()
+            .map(|message| format!("Config error: {message}"));
+
+        let document_error = self.active_document().error.as_ref().map(|error| match error {
+            Error::DialogClosed => String::from("DialogClosed"),
+            Error::IOFailed(err_kind) => format!("IO Error: {}", err_kind.to_string()),
+        });
+
+        config_error
+            .into_iter()
+            .chain(document_error)
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    fn title(&self) -> String {
+        String::from("A cool editor!")
+    }
+
+    pub fn theme(&self) -> Theme {
+        if self.theme.is_dark() {
+            Theme::Dark
+        } else {
+            Theme::Light
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::Edit(action) => {
+                let is_edit = action.is_edit();
+
+                self.active_document_mut().perform_edit(action, self.auto_pairs);
+
+                let document = self.active_document();
+
+                if self.auto_save && is_edit && document.path.is_some() {
+                    let id = document.id;
+                    let version = document.edit_version;
+
+                    Task::perform(debounce(AUTO_SAVE_DEBOUNCE), move |_| {
+                        Message::AutoSaveTick { id, version }
+                    })
+                } else {
+                    Task::none()
+                }
+            }
+            Message::FileOpened(Ok((path, content))) => {
+                tracing::debug!(?path, "file opened");
+
+                if self.awaiting_startup_load {
+                    let id = self.documents[0].id;
+                    self.documents[0] = Document::opened(id, path, &content);
+                    self.active = 0;
+                } else {
+                    let id = self.allocate_document_id();
+                    self.documents.push(Document::opened(id, path, &content));
+                    self.active = self.documents.len() - 1;
+                }
+
+                self.awaiting_startup_load = false;
+                Task::none()
+            }
+            Message::FileOpened(Err(error)) => {
+                tracing::warn!(?error, "failed to open file");
+                self.awaiting_startup_load = false;
+                self.active_document_mut().error = Some(error);
+                Task::none()
+            }
+            Message::Open => Task::perform(pick_file(), Message::FileOpened),
+            Message::New => {
+                if self.active_document().is_modified {
+                    self.confirm_discard(PendingAction::New)
+                } else {
+                    self.active_document_mut().reset();
+                    Task::none()
+                }
+            }
+            Message::NewTab => {
+                let id = self.allocate_document_id();
+                self.documents.push(Document::new(id));
+                self.active = self.documents.len() - 1;
+                Task::none()
+            }
+            Message::SelectTab(index) => {
+                if index < self.documents.len() {
+                    self.active = index;
+                }
+                Task::none()
+            }
+            Message::CloseTab(index) => {
+                let is_modified = self
+                    .documents
+                    .get(index)
+                    .map(|document| document.is_modified)
+                    .unwrap_or(false);
+
+                if is_modified {
+                    self.active = index;
+                    self.confirm_discard(PendingAction::CloseTab(index))
+                } else {
+                    self.close_tab(index);
+                    Task::none()
+                }
+            }
+            Message::NextTab => {
+                self.active = (self.active + 1) % self.documents.len();
+                Task::none()
+            }
+            Message::Save => {
+                let document = self.active_document();
+                let id = document.id;
+                let contents = document.content.text();
+                let path = document.path.clone();
+
+                Task::perform(save_file(path, contents), move |result| {
+                    Message::FileSaved {
+                        id,
+                        result,
+                        pending: None,
+                    }
+                })
+            }
+            Message::FileSaved { id, result, pending } => match result {
+                Ok(path) => {
+                    tracing::debug!(?path, "file saved");
+                    if let Some(document) = self.document_mut(id) {
+                        document.path = Some(path);
+                        document.is_modified = false;
+                    }
+
+                    match pending {
+                        Some(pending) => self.apply_pending(pending),
+                        None => Task::none(),
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!(?error, "failed to save file");
+                    if let Some(document) = self.document_mut(id) {
+                        document.error = Some(error);
+                    }
+
+                    Task::none()
+                }
+            },
+            Message::ThemeSelected(theme) => {
+                self.theme = theme;
+                Task::none()
+            }
+            Message::CloseRequested(id) => {
+                if self.documents.iter().any(|document| document.is_modified) {
+                    self.confirm_discard(PendingAction::Quit(id))
+                } else {
+                    window::close(id)
+                }
+            }
+            Message::ConfirmDiscard { pending, choice } => match choice {
+                DiscardChoice::Save => match pending {
+                    PendingAction::Quit(_) => self.save_all_then(pending),
+                    PendingAction::New | PendingAction::CloseTab(_) => {
+                        let document = self.active_document();
+                        let id = document.id;
+                        let contents = document.content.text();
+                        let path = document.path.clone();
+
+                        Task::perform(save_file(path, contents), move |result| {
+                            Message::FileSaved {
+                                id,
+                                result,
+                                pending: Some(pending),
+                            }
+                        })
+                    }
+                },
+                DiscardChoice::Discard => self.apply_pending(pending),
+                DiscardChoice::Cancel => Task::none(),
+            },
+            Message::AutoSaveTick { id, version } => {
+                if !self.auto_save_tick_is_current(id, version) {
+                    return Task::none();
+                }
+
+                let document = self.active_document();
+                if document.path.is_none() || !document.is_modified {
+                    return Task::none();
+                }
+
+                let id = document.id;
+                let contents = document.content.text();
+                let path = document.path.clone();
+
+                Task::perform(save_file(path, contents), move |result| {
+                    Message::FileSaved {
+                        id,
+                        result,
+                        pending: None,
+                    }
+                })
+            }
+            Message::ExportHtml => {
+                let document = self.active_document();
+                let contents = document.content.text();
+                let extension = document.extension().to_string();
+
+                Task::perform(
+                    export::export_html(None, contents, extension, self.theme),
+                    Message::HtmlExported,
+                )
+            }
+            Message::HtmlExported(Ok(_path)) => Task::none(),
+            Message::HtmlExported(Err(error)) => {
+                self.active_document_mut().error = Some(error);
+                Task::none()
+            }
+            Message::TabSaved { id, result } => {
+                match result {
+                    Ok(path) => {
+                        tracing::debug!(?path, "file saved");
+                        if let Some(document) = self.document_mut(id) {
+                            document.path = Some(path);
+                            document.is_modified = false;
+                        }
+                    }
+                    Err(error) => {
+                        tracing::warn!(?error, "failed to save file");
+                        if let Some(document) = self.document_mut(id) {
+                            document.error = Some(error);
+                        }
+                        self.pending_action = None;
+                    }
+                }
+
+                self.pending_saves = self.pending_saves.saturating_sub(1);
+
+                if self.pending_saves == 0 {
+                    match self.pending_action.take() {
+                        Some(pending) => self.apply_pending(pending),
+                        None => Task::none(),
+                    }
+                } else {
+                    Task::none()
+                }
+            }
+        }
+    }
+
+    fn view(&self) -> Element<Message> {
+        let document = self.active_document();
+
+        let controls = row![
+            action(new_icon(), "New", Some(Message::New)),
+            action(open_icon(), "Open", Some(Message::Open)),
+            action(
+                save_icon(),
+                "Save",
+                document.is_modified.then_some(Message::Save)
+            ),
+            action(export_icon(), "Export as HTML", Some(Message::ExportHtml)),
+            horizontal_space(),
+            pick_list(
+                highlighter::Theme::ALL,
+                Some(self.theme),
+                Message::ThemeSelected
+            ),
+        ]
+        .spacing(10);
+
+        let input = text_editor(&document.content)
+            .on_action(Message::Edit)
+            .height(Length::Fill)
+            .highlight(document.extension(), self.theme);
+
+        let status_bar = {
+            let file_path = if let Some(Error::IOFailed(error)) = document.error.as_ref() {
+                text(error.to_string())
+            } else {
+                match document.path.as_deref().map(Path::to_str) {
+                    Some(Some(path)) => text(path).size(14),
+                    None => text("New File"),
+                    _ => text(""),
+                }
+            };
+
+            let error_msg = text(self.status_message());
+
+            let position = {
+                let (line, column) = document.content.cursor_position();
+                text(format!("{}:{}", line + 1, column + 1))
+            };
+
+            row![
+                file_path,
+                horizontal_space(),
+                error_msg,
+                Space::with_width(10),
+                position
+            ]
+        };
+
+        let body = column![self.tab_bar(), controls, input, status_bar].spacing(5);
+
+        container(body).padding(10).into()
+    }
+
+    fn tab_bar(&self) -> Element<Message> {
+        let tabs = self.documents.iter().enumerate().map(|(index, document)| {
+            let label = if document.is_modified {
+                format!("{} *", document.title())
+            } else {
+                document.title()
+            };
+
+            let select = button(text(label).size(14))
+                .on_press(Message::SelectTab(index))
+                .padding([4, 8])
+                .style(move |theme, status| {
+                    if index == self.active {
+                        button::primary(theme, status)
+                    } else {
+                        button::secondary(theme, status)
+                    }
+                });
+
+            let close = button(text("x").size(14))
+                .on_press(Message::CloseTab(index))
+                .padding([4, 8])
+                .style(button::secondary);
+
+            row![select, close].spacing(2).into()
+        });
+
+        row(tabs)
+            .push(
+                button(text("+").size(14))
+                    .on_press(Message::NewTab)
+                    .padding([4, 8]),
+            )
+            .spacing(4)
+            .into()
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        let keybindings = self.keybindings.clone();
+
+        Subscription::batch([
+            keyboard::on_key_press(move |key_code, modifiers| {
+                keybindings
+                    .iter()
+                    .find(|binding| binding.matches(&key_code, modifiers))
+                    .map(|binding| binding.action.to_message())
+            }),
+            window::close_requests().map(Message::CloseRequested),
+        ])
+    }
+
+    /// Defers `pending` behind a Save/Discard/Cancel prompt.
+    fn confirm_discard(&self, pending: PendingAction) -> Task<Message> {
+        Task::perform(confirm_discard(), move |choice| Message::ConfirmDiscard {
+            pending,
+            choice,
+        })
+    }
+
+    /// Saves every modified document, then carries out `pending` once they've
+    /// all finished writing. Used when quitting, since an unsaved-changes
+    /// prompt at that point must not let any other dirty tab's edits vanish.
+    fn save_all_then(&mut self, pending: PendingAction) -> Task<Message> {
+        let modified: Vec<&Document> = self
+            .documents
+            .iter()
+            .filter(|document| document.is_modified)
+            .collect();
+
+        if modified.is_empty() {
+            return self.apply_pending(pending);
+        }
+
+        self.pending_saves = modified.len();
+        self.pending_action = Some(pending);
+
+        Task::batch(modified.into_iter().map(|document| {
+            let id = document.id;
+            let contents = document.content.text();
+            let path = document.path.clone();
+
+            Task::perform(save_file(path, contents), move |result| {
+                Message::TabSaved { id, result }
+            })
+        }))
+    }
+
+    /// Carries out an action that was queued behind an unsaved-changes prompt.
+    fn apply_pending(&mut self, pending: PendingAction) -> Task<Message> {
+        match pending {
+            PendingAction::New => {
+                self.active_document_mut().reset();
+                Task::none()
+            }
+            PendingAction::CloseTab(index) => {
+                self.close_tab(index);
+                Task::none()
+            }
+            PendingAction::Quit(id) => window::close(id),
+        }
+    }
+
+    /// Removes the document at `index`, always leaving at least one tab open
+    /// and keeping `active` pointed at a valid document.
+    fn close_tab(&mut self, index: usize) {
+        if index >= self.documents.len() {
+            return;
+        }
+
+        self.documents.remove(index);
+
+        if self.documents.is_empty() {
+            let id = self.allocate_document_id();
+            self.documents.push(Document::new(id));
+            self.active = 0;
+        } else if self.active >= self.documents.len() {
+            self.active = self.documents.len() - 1;
+        } else if self.active > index {
+            self.active -= 1;
+        }
+    }
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
+}
+
+async fn load_file(path: PathBuf) -> Result<(PathBuf, Arc<String>), Error> {
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map(Arc::new)
+        .map_err(|error| error.kind())
+        .map_err(Error::IOFailed)?;
+
+    Ok((path, content))
+}
+
+async fn pick_file() -> Result<(PathBuf, Arc<String>), Error> {
+    let handle = rfd::AsyncFileDialog::new()
+        .set_title("Choose a text file...")
+        .pick_file()
+        .await
+        .ok_or(Error::DialogClosed)?;
+
+    load_file(handle.path().to_owned()).await
+}
+
+/// Writes `text` to `path`, prompting for a destination first if `path` is
+/// `None`.
+pub async fn save_file(path: Option<PathBuf>, text: String) -> Result<PathBuf, Error> {
+    let path = if let Some(path) = path {
+        path
+    } else {
+        rfd::AsyncFileDialog::new()
+            .set_title("Choose a file name...")
+            .save_file()
+            .await
+            .ok_or(Error::DialogClosed)
+            .map(|handle| handle.path().to_owned())?
+    };
+
+    tokio::fs::write(&path, text)
+        .await
+        .map_err(|error| Error::IOFailed(error.kind()))?;
+
+    Ok(path)
+}
+
+async fn debounce(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+async fn confirm_discard() -> DiscardChoice {
+    let result = rfd::AsyncMessageDialog::new()
+        .set_title("Unsaved changes")
+        .set_description("Do you want to save your changes before continuing?")
+        .set_buttons(rfd::MessageButtons::YesNoCancel)
+        .set_level(rfd::MessageLevel::Warning)
+        .show()
+        .await;
+
+    match result {
+        rfd::MessageDialogResult::Yes => DiscardChoice::Save,
+        rfd::MessageDialogResult::No => DiscardChoice::Discard,
+        _ => DiscardChoice::Cancel,
+    }
+}
+
+fn default_file() -> PathBuf {
+    PathBuf::from(format!("{}/src/main.rs", env!("CARGO_MANIFEST_DIR")))
+}
+
+#[derive(Debug, Clone)]
+pub enum Error {
+    DialogClosed,
+    IOFailed(io::ErrorKind),
+}
+
+struct TokioExecutor(tokio::runtime::Runtime);
+
+impl iced::Executor for TokioExecutor {
+    fn new() -> Result<Self, io::Error>
+    where
+        Self: Sized,
+    {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map(Self)
+    }
+
+    fn spawn(&self, future: impl std::future::Future<Output = ()> + MaybeSend + 'static) {
+        let _ = tokio::runtime::Runtime::spawn(&self.0, future);
+    }
+
+    fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _guard = tokio::runtime::Runtime::enter(&self.0);
+        f()
+    }
+}
+
+fn action<'a>(
+    content: Element<'a, Message>,
+    label: &'a str,
+    on_press: Option<Message>,
+) -> Element<'a, Message> {
+    let is_modified = on_press.is_some();
+    tooltip(
+        button(container(content).center_x(30))
+            .on_press_maybe(on_press)
+            .padding([5, 10])
+            .style(move |theme, status| {
+                if is_modified {
+                    button::primary(theme, status)
+                } else {
+                    button::secondary(theme, status)
+                }
+            }),
+        label,
+        tooltip::Position::FollowCursor,
+    )
+    .into()
+}
+
+fn new_icon<'a>() -> Element<'a, Message> {
+    icon('\u{e800}')
+}
+
+fn save_icon<'a>() -> Element<'a, Message> {
+    icon('\u{e801}')
+}
+
+fn open_icon<'a>() -> Element<'a, Message> {
+    icon('\u{f115}')
+}
+
+fn export_icon<'a>() -> Element<'a, Message> {
+    text("HTML").size(12).into()
+}
+
+fn icon<'a>(codepoint: char) -> Element<'a, Message> {
+    const ICON_FONT: Font = Font::with_name("editor-icon");
+
+    text(codepoint).font(ICON_FONT).into()
+}
@@ -0,0 +1,279 @@
+//! Headless integration tests that drive `Editor::update` directly, without
+//! opening a window. Set `LOG_LEVEL=debug` (or any `tracing`-style filter) to
+//! see the same logging `run()` would produce.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use iced::widget::text_editor;
+use iced_editor_example::{init_tracing, save_file, Config, Editor, Error, Message};
+
+/// Builds an `Editor` with tracing wired up the same way `run()` does, so a
+/// single `LOG_LEVEL` env var surfaces debug output across every test.
+fn editor() -> Editor {
+    init_tracing();
+    Editor::new(Config::default())
+}
+
+/// Opens `path` with `contents` through `Message::FileOpened`, as if the
+/// user had picked it from the file dialog.
+fn open(editor: &mut Editor, path: PathBuf, contents: &str) {
+    let _ = editor.update(Message::FileOpened(Ok((path, Arc::new(contents.to_string())))));
+}
+
+fn insert(editor: &mut Editor, text: &str) {
+    for c in text.chars() {
+        let _ = editor.update(Message::Edit(text_editor::Action::Edit(
+            text_editor::Edit::Insert(c),
+        )));
+    }
+}
+
+fn backspace(editor: &mut Editor) {
+    let _ = editor.update(Message::Edit(text_editor::Action::Edit(
+        text_editor::Edit::Backspace,
+    )));
+}
+
+#[test]
+fn opening_a_file_appends_a_new_tab() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    std::fs::write(&path, "hello").unwrap();
+
+    let mut editor = editor();
+    open(&mut editor, path.clone(), "hello");
+
+    assert_eq!(editor.documents().len(), 2);
+    assert_eq!(editor.active_document().path, Some(path));
+    assert_eq!(editor.active_document().content.text(), "hello\n");
+    assert!(!editor.active_document().is_modified);
+    assert!(editor.active_document().error.is_none());
+}
+
+#[test]
+fn failed_open_surfaces_an_error_on_the_active_document() {
+    let mut editor = editor();
+    let _ = editor.update(Message::FileOpened(Err(Error::DialogClosed)));
+
+    assert!(matches!(editor.active_document().error, Some(Error::DialogClosed)));
+}
+
+#[test]
+fn startup_load_replaces_the_blank_placeholder_instead_of_appending_a_tab() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("default.rs");
+    std::fs::write(&path, "fn main() {}").unwrap();
+
+    init_tracing();
+    let (mut editor, _task) = Editor::initialize(Config::default(), None);
+    assert_eq!(editor.documents().len(), 1);
+
+    open(&mut editor, path.clone(), "fn main() {}");
+
+    assert_eq!(editor.documents().len(), 1);
+    assert_eq!(editor.active_document().path, Some(path));
+
+    // A later, manual Open still appends rather than replacing.
+    let dir2 = tempfile::tempdir().unwrap();
+    let other = dir2.path().join("notes.txt");
+    open(&mut editor, other.clone(), "notes");
+
+    assert_eq!(editor.documents().len(), 2);
+    assert_eq!(editor.active_document().path, Some(other));
+}
+
+#[test]
+fn failed_startup_load_leaves_a_single_tab_and_does_not_suppress_later_opens() {
+    init_tracing();
+    let (mut editor, _task) = Editor::initialize(Config::default(), None);
+    let _ = editor.update(Message::FileOpened(Err(Error::DialogClosed)));
+
+    assert_eq!(editor.documents().len(), 1);
+    assert!(matches!(editor.active_document().error, Some(Error::DialogClosed)));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    open(&mut editor, path.clone(), "notes");
+
+    assert_eq!(editor.documents().len(), 2);
+}
+
+#[test]
+fn editing_marks_the_buffer_modified_and_moves_the_cursor() {
+    let mut editor = editor();
+    insert(&mut editor, "fn main() {}");
+
+    assert!(editor.active_document().is_modified);
+    assert_eq!(editor.active_document().content.text(), "fn main() {}\n");
+    assert_eq!(editor.active_document().content.cursor_position(), (0, 12));
+}
+
+#[test]
+fn status_message_shows_a_document_error_alongside_a_lingering_config_error() {
+    init_tracing();
+    let (mut editor, _task) =
+        Editor::initialize(Config::default(), Some(String::from("unknown field `nonsense`")));
+
+    assert_eq!(
+        editor.status_message(),
+        "Config error: unknown field `nonsense`"
+    );
+
+    let _ = editor.update(Message::FileOpened(Err(Error::DialogClosed)));
+
+    let message = editor.status_message();
+    assert!(message.contains("Config error: unknown field `nonsense`"));
+    assert!(message.contains("DialogClosed"));
+}
+
+#[test]
+fn theme_selected_updates_the_editor_theme() {
+    let mut editor = editor();
+    let _ = editor.update(Message::ThemeSelected(iced::highlighter::Theme::Base16Light));
+
+    assert_eq!(editor.theme(), iced::Theme::Light);
+}
+
+#[test]
+fn backspace_deletes_an_auto_paired_bracket() {
+    let mut editor = editor();
+    insert(&mut editor, "(");
+    assert_eq!(editor.active_document().content.text(), "()\n");
+
+    backspace(&mut editor);
+
+    assert_eq!(editor.active_document().content.text(), "\n");
+}
+
+#[test]
+fn auto_save_tick_stays_current_after_an_earlier_tab_closes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path_a = dir.path().join("a.txt");
+    let path_b = dir.path().join("b.txt");
+    std::fs::write(&path_a, "a").unwrap();
+    std::fs::write(&path_b, "b").unwrap();
+
+    let mut editor = editor();
+    open(&mut editor, path_a, "a");
+    open(&mut editor, path_b, "b");
+
+    // `b.txt` is active; editing it schedules a tick keyed by its own id.
+    insert(&mut editor, "!");
+    let id = editor.active_document().id;
+    let version = editor.active_document().edit_version;
+
+    // Close the tab before `b.txt`'s slot, shifting every later document
+    // down a position in the `Vec` (but not in identity).
+    let _ = editor.update(Message::CloseTab(0));
+
+    assert!(editor.auto_save_tick_is_current(id, version));
+}
+
+#[test]
+fn auto_save_tick_goes_stale_once_a_different_tab_becomes_active() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("draft.md");
+
+    let mut editor = editor();
+    open(&mut editor, path, "draft");
+    insert(&mut editor, "!");
+
+    let id = editor.active_document().id;
+    let version = editor.active_document().edit_version;
+
+    // Switch away before the debounce fires.
+    let _ = editor.update(Message::NewTab);
+
+    assert!(!editor.auto_save_tick_is_current(id, version));
+}
+
+#[test]
+fn file_saved_targets_the_saved_document_even_after_the_active_tab_changes() {
+    let mut editor = editor();
+    let saved_id = editor.active_document().id;
+
+    // A second tab becomes active while the first tab's save is in flight.
+    let _ = editor.update(Message::NewTab);
+    assert_ne!(editor.active_document().id, saved_id);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("first.txt");
+    let _ = editor.update(Message::FileSaved {
+        id: saved_id,
+        result: Ok(path.clone()),
+        pending: None,
+    });
+
+    let first = editor
+        .documents()
+        .iter()
+        .find(|document| document.id == saved_id)
+        .unwrap();
+    assert_eq!(first.path, Some(path));
+    assert!(!first.is_modified);
+
+    // The tab that was active when the save completed is untouched.
+    assert_eq!(editor.active_document().path, None);
+}
+
+#[test]
+fn tab_saved_targets_the_saved_document_even_after_an_earlier_tab_closes() {
+    let mut editor = editor();
+    let clean_id = editor.active_document().id;
+
+    let _ = editor.update(Message::NewTab);
+    insert(&mut editor, "a");
+    let dirty_a_id = editor.active_document().id;
+
+    let _ = editor.update(Message::NewTab);
+    insert(&mut editor, "b");
+    let dirty_b_id = editor.active_document().id;
+
+    // A quit would have dispatched saves for the two dirty tabs here, at
+    // indices 1 and 2. Before either resolves, the unmodified tab ahead of
+    // them closes without a confirmation prompt, shifting every later
+    // document down a `Vec` position.
+    let _ = editor.update(Message::CloseTab(0));
+    assert!(!editor.documents().iter().any(|document| document.id == clean_id));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("b.txt");
+    let _ = editor.update(Message::TabSaved {
+        id: dirty_b_id,
+        result: Ok(path.clone()),
+    });
+
+    let a = editor
+        .documents()
+        .iter()
+        .find(|document| document.id == dirty_a_id)
+        .unwrap();
+    assert!(a.is_modified, "the unrelated tab must not be marked saved");
+
+    let b = editor
+        .documents()
+        .iter()
+        .find(|document| document.id == dirty_b_id)
+        .unwrap();
+    assert_eq!(b.path, Some(path));
+    assert!(!b.is_modified);
+}
+
+#[tokio::test]
+async fn edits_round_trip_through_save_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("draft.md");
+
+    let mut editor = editor();
+    open(&mut editor, path.clone(), "draft");
+    insert(&mut editor, "!");
+
+    let contents = editor.active_document().content.text();
+    let saved = save_file(Some(path.clone()), contents.clone())
+        .await
+        .expect("save_file should succeed with an explicit path");
+
+    assert_eq!(saved, path);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
+}